@@ -0,0 +1,81 @@
+/// Trait for cached values that can be modified in place and need to report
+/// whether they hold unflushed changes.
+///
+/// This is the basis of write-back caching: a backing store is only written
+/// to for entries that are actually dirty, rather than on every eviction.
+pub trait Cacheable {
+    /// Returns `true` if this value has unflushed modifications.
+    fn dirty(&self) -> bool;
+}
+
+/// Wraps a cached value together with a dirty bit, so that callers reading
+/// and writing through [`get_mut`](Dirty::get_mut) automatically mark the
+/// value as having unflushed modifications.
+///
+/// A [`Dirty<T>`] dropped or evicted from a cache (e.g. via
+/// [`VecCache::push_evict`](crate::multi::VecCache::push_evict)) still
+/// reports its own dirtiness, so a caller performing write-back caching can
+/// check the evicted value before discarding it, flushing it to a backing
+/// store if [`dirty`](Cacheable::dirty) returns `true`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Dirty<T> {
+    value: T,
+    dirty: bool,
+}
+
+impl<T> Dirty<T> {
+    /// Wraps `value`, marking it dirty (freshly inserted, not yet flushed).
+    #[must_use]
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self { value, dirty: true }
+    }
+
+    /// Wraps `value`, marking it clean, e.g. because it was just loaded from
+    /// a backing store and has no unflushed modifications yet.
+    #[must_use]
+    #[inline]
+    pub fn clean(value: T) -> Self {
+        Self { value, dirty: false }
+    }
+
+    /// Returns an immutable reference to the wrapped value, without affecting
+    /// its dirty bit.
+    #[inline]
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns a mutable reference to the wrapped value, marking it dirty.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.dirty = true;
+        &mut self.value
+    }
+
+    /// Marks this value as clean, typically called once its state has been
+    /// persisted to a backing store.
+    #[inline]
+    pub fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Unwraps this [`Dirty<T>`], discarding its dirty bit.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Cacheable for Dirty<T> {
+    #[inline]
+    fn dirty(&self) -> bool {
+        self.dirty
+    }
+}
+
+impl<T> From<T> for Dirty<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::multi::limit::OccupancyLimit;
+
+type EntryId = usize;
+type FreqId = usize;
+
+/// A node in the doubly linked list of distinct access frequencies, ordered
+/// ascending from the head (lowest frequency, i.e. next to be evicted).
+struct FreqNode {
+    freq: usize,
+    /// Entries currently at this frequency. Order is not meaningful; any
+    /// member may be evicted when this is the head node.
+    members: Vec<EntryId>,
+    prev: Option<FreqId>,
+    next: Option<FreqId>,
+}
+
+struct Slot<K, V> {
+    key: K,
+    value: V,
+    /// The frequency node this entry currently belongs to.
+    node: FreqId,
+    /// This entry's index within `node`'s `members`, kept in sync so it can
+    /// be removed from the middle of that list in O(1) via `swap_remove`.
+    pos: usize,
+}
+
+/// Constant-time (amortized) least-frequently-used eviction cache.
+///
+/// Implements the standard O(1) LFU structure: a doubly linked list of
+/// frequency nodes ordered by access count, where each frequency node owns the
+/// set of entries sharing that count, and a lookup map from key to entry.
+/// On every hit, an entry is detached from its current frequency node and
+/// re-attached to the node for `freq + 1` (creating it if absent), dropping
+/// the old node if it is left empty. On eviction, an entry is removed from the
+/// lowest-frequency node, i.e. the head of the list.
+///
+/// Requires `K: Clone` because an evicted or queried key must be produced
+/// independently of the lookup map that still needs its own copy to index by.
+pub struct LfuCache<K: Eq + Hash + Clone, V> {
+    index: HashMap<K, EntryId>,
+    slots: Vec<Option<Slot<K, V>>>,
+    free_slots: Vec<EntryId>,
+    nodes: Vec<Option<FreqNode>>,
+    free_nodes: Vec<FreqId>,
+    head: Option<FreqId>,
+    limit: OccupancyLimit,
+}
+
+impl<K: Eq + Hash + Clone, V> Default for LfuCache<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> LfuCache<K, V> {
+    /// Creates a new, empty [`LfuCache<K, V>`] with an unrestricted maximum
+    /// occupancy.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            index: HashMap::new(),
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+            nodes: Vec::new(),
+            free_nodes: Vec::new(),
+            head: None,
+            limit: OccupancyLimit::Unlimited,
+        }
+    }
+
+    /// Creates a new, empty [`LfuCache<K, V>`] with a fixed upper bound on
+    /// maximum occupancy.
+    #[must_use]
+    pub fn with_limit(max_occupancy: usize) -> Self {
+        Self { limit: OccupancyLimit::Limited(max_occupancy), ..Self::new() }
+    }
+
+    /// Returns the current occupancy (total number of entries) of the cache.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns `true` if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Un-sets the maximum occupancy limit of this [`LfuCache<K, V>`],
+    /// returning the old limit.
+    #[inline]
+    pub fn unset_limit(&mut self) -> Option<usize> {
+        self.limit.unset_limit()
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.limit.get()
+    }
+
+    /// Returns a reference to the value associated with `key`, bumping its
+    /// access frequency, if it is currently cached.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let id = *self.index.get(key)?;
+        self.bump(id);
+        self.slots[id].as_ref().map(|slot| &slot.value)
+    }
+
+    /// Inserts `(key, value)` into the cache.
+    ///
+    /// If `key` is already present, its value is replaced and its frequency
+    /// is bumped, and `None` is returned. Otherwise, the entry is inserted
+    /// fresh at frequency 1, evicting the entry at the lowest frequency first
+    /// if the cache is at capacity; the evicted `(key, value)` pair, if any,
+    /// is returned so that a caller performing write-back caching can persist
+    /// it before it is lost. Under a limit of `0`, a new key is dropped without
+    /// being inserted and `None` is returned.
+    pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some(&id) = self.index.get(&key) {
+            self.slots[id].as_mut().unwrap().value = value;
+            self.bump(id);
+            return None;
+        }
+        // A zero limit admits nothing; skip the insert entirely rather than
+        // evicting (nothing to evict from an empty cache) and inserting anyway.
+        if self.limit.is_zero() {
+            return None;
+        }
+        let evicted = match self.limit.get() {
+            Some(lim) if self.len() + 1 > lim => self.evict_one(),
+            _ => None,
+        };
+        self.insert_new(key, value);
+        evicted
+    }
+
+    /// Inserts a brand-new key at frequency 1, creating (or reusing) the
+    /// frequency-1 node at the head of the list.
+    fn insert_new(&mut self, key: K, value: V) {
+        let node_id = match self.head {
+            Some(head) if self.nodes[head].as_ref().unwrap().freq == 1 => head,
+            Some(head) => self.insert_node_before(head, 1),
+            None => self.alloc_head_node(1),
+        };
+        let slot_id = self.alloc_slot(Slot { key: key.clone(), value, node: node_id, pos: 0 });
+        self.push_member(node_id, slot_id);
+        self.index.insert(key, slot_id);
+    }
+
+    /// Detaches `id` from its current frequency node and re-attaches it to
+    /// the node for `freq + 1`, creating that node if it does not already
+    /// immediately follow the current one, and dropping the current node if
+    /// it is left empty.
+    fn bump(&mut self, id: EntryId) {
+        let node_id = self.slots[id].as_ref().unwrap().node;
+        let freq = self.nodes[node_id].as_ref().unwrap().freq;
+        self.remove_member(node_id, id);
+
+        let next = self.nodes[node_id].as_ref().unwrap().next;
+        let next_node_id = match next {
+            Some(n) if self.nodes[n].as_ref().unwrap().freq == freq + 1 => n,
+            _ => self.insert_node_after(node_id, freq + 1),
+        };
+        self.push_member(next_node_id, id);
+        self.slots[id].as_mut().unwrap().node = next_node_id;
+
+        if self.nodes[node_id].as_ref().unwrap().members.is_empty() {
+            self.remove_node(node_id);
+        }
+    }
+
+    /// Removes and returns the `(key, value)` pair at the lowest frequency,
+    /// i.e. an arbitrary member of the head node.
+    fn evict_one(&mut self) -> Option<(K, V)> {
+        let head = self.head?;
+        let slot_id = self.nodes[head].as_mut().unwrap().members.pop()?;
+        let slot = self.slots[slot_id].take().unwrap();
+        self.free_slots.push(slot_id);
+        self.index.remove(&slot.key);
+        if self.nodes[head].as_ref().unwrap().members.is_empty() {
+            self.remove_node(head);
+        }
+        Some((slot.key, slot.value))
+    }
+
+    fn push_member(&mut self, node_id: FreqId, slot_id: EntryId) {
+        let node = self.nodes[node_id].as_mut().unwrap();
+        let pos = node.members.len();
+        node.members.push(slot_id);
+        self.slots[slot_id].as_mut().unwrap().pos = pos;
+    }
+
+    fn remove_member(&mut self, node_id: FreqId, slot_id: EntryId) {
+        let node = self.nodes[node_id].as_mut().unwrap();
+        let pos = self.slots[slot_id].as_ref().unwrap().pos;
+        node.members.swap_remove(pos);
+        if let Some(&moved_id) = node.members.get(pos) {
+            self.slots[moved_id].as_mut().unwrap().pos = pos;
+        }
+    }
+
+    fn alloc_slot(&mut self, slot: Slot<K, V>) -> EntryId {
+        if let Some(id) = self.free_slots.pop() {
+            self.slots[id] = Some(slot);
+            id
+        } else {
+            self.slots.push(Some(slot));
+            self.slots.len() - 1
+        }
+    }
+
+    fn alloc_node(&mut self, node: FreqNode) -> FreqId {
+        if let Some(id) = self.free_nodes.pop() {
+            self.nodes[id] = Some(node);
+            id
+        } else {
+            self.nodes.push(Some(node));
+            self.nodes.len() - 1
+        }
+    }
+
+    fn alloc_head_node(&mut self, freq: usize) -> FreqId {
+        let id = self.alloc_node(FreqNode { freq, members: Vec::new(), prev: None, next: self.head });
+        if let Some(old_head) = self.head {
+            self.nodes[old_head].as_mut().unwrap().prev = Some(id);
+        }
+        self.head = Some(id);
+        id
+    }
+
+    fn insert_node_before(&mut self, before: FreqId, freq: usize) -> FreqId {
+        let prev = self.nodes[before].as_ref().unwrap().prev;
+        let id = self.alloc_node(FreqNode { freq, members: Vec::new(), prev, next: Some(before) });
+        self.nodes[before].as_mut().unwrap().prev = Some(id);
+        match prev {
+            Some(p) => self.nodes[p].as_mut().unwrap().next = Some(id),
+            None => self.head = Some(id),
+        }
+        id
+    }
+
+    fn insert_node_after(&mut self, after: FreqId, freq: usize) -> FreqId {
+        let next = self.nodes[after].as_ref().unwrap().next;
+        let id = self.alloc_node(FreqNode { freq, members: Vec::new(), prev: Some(after), next });
+        self.nodes[after].as_mut().unwrap().next = Some(id);
+        if let Some(n) = next {
+            self.nodes[n].as_mut().unwrap().prev = Some(id);
+        }
+        id
+    }
+
+    fn remove_node(&mut self, id: FreqId) {
+        let node = self.nodes[id].take().unwrap();
+        if let Some(p) = node.prev {
+            self.nodes[p].as_mut().unwrap().next = node.next;
+        }
+        if let Some(n) = node.next {
+            self.nodes[n].as_mut().unwrap().prev = node.prev;
+        }
+        if self.head == Some(id) {
+            self.head = node.next;
+        }
+        self.free_nodes.push(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LfuCache;
+
+    #[test]
+    fn zero_limit_insert_admits_nothing() {
+        let mut cache: LfuCache<u8, u8> = LfuCache::with_limit(0);
+        let evicted = cache.insert(1, 1);
+        assert_eq!(evicted, None);
+        assert_eq!(cache.len(), 0);
+        assert!(cache.get(&1).is_none());
+    }
+
+    #[test]
+    fn evicts_least_frequently_used_at_capacity() {
+        let mut cache: LfuCache<u8, u8> = LfuCache::with_limit(2);
+        assert_eq!(cache.insert(1, 1), None);
+        assert_eq!(cache.insert(2, 2), None);
+        // Bump key 1's frequency above key 2's, so key 2 is evicted next.
+        cache.get(&1);
+        let evicted = cache.insert(3, 3);
+        assert_eq!(evicted, Some((2, 2)));
+        assert_eq!(cache.len(), 2);
+    }
+}
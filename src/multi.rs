@@ -1,7 +1,11 @@
-use std::collections::{LinkedList, VecDeque};
+use std::collections::{LinkedList, TryReserveError, VecDeque};
 
 pub mod limit;
 
+use limit::MemSize;
+
+use crate::cacheable::Cacheable;
+
 /// [VecDeque]-based FIFO cache structure for storing values that may be dropped
 /// if enough newer values are added
 ///
@@ -15,12 +19,16 @@ pub mod limit;
 pub struct VecCache<T> {
     storage: VecDeque<T>,
     limit: limit::OccupancyLimit,
+    /// Running total of [`MemSize::mem_size`] over `storage`, kept up to date
+    /// so that [`limit::OccupancyLimit::LimitedBytes`] budgets can be enforced
+    /// without re-summing the whole cache on every push.
+    bytes: usize,
 }
 
 impl<T> Default for VecCache<T> where VecDeque<T>: Default {
     #[inline]
     fn default() -> Self {
-        Self { storage: VecDeque::new(), limit: limit::OccupancyLimit::Unlimited }
+        Self { storage: VecDeque::new(), limit: limit::OccupancyLimit::Unlimited, bytes: 0 }
     }
 }
 
@@ -37,7 +45,7 @@ impl<T> VecCache<T> {
     #[must_use]
     #[inline]
     pub fn new() -> Self {
-        Self { storage: VecDeque::new(), limit: limit::OccupancyLimit::Unlimited }
+        Self { storage: VecDeque::new(), limit: limit::OccupancyLimit::Unlimited, bytes: 0 }
     }
 
     /// Creates a new [`VecCache<T>`] with a fixed upper bound on maximum occupancy
@@ -47,7 +55,39 @@ impl<T> VecCache<T> {
     #[must_use]
     #[inline]
     pub fn with_limit(max_occupancy: usize) -> Self {
-        Self { storage: VecDeque::with_capacity(max_occupancy), limit: limit::OccupancyLimit::Limited(max_occupancy) }
+        Self { storage: VecDeque::with_capacity(max_occupancy), limit: limit::OccupancyLimit::Limited(max_occupancy), bytes: 0 }
+    }
+
+    /// Fallibly creates a new [`VecCache<T>`] with a fixed upper bound on maximum
+    /// occupancy, reporting allocation failure instead of aborting.
+    ///
+    /// Unlike [`with_limit`], this uses [`VecDeque::try_reserve_exact`] rather than
+    /// [`VecDeque::with_capacity`], making it suitable for memory-constrained uses
+    /// where an allocation failure must be handled rather than panicking.
+    pub fn try_with_limit(max_occupancy: usize) -> Result<Self, TryReserveError> {
+        let mut storage = VecDeque::new();
+        storage.try_reserve_exact(max_occupancy)?;
+        Ok(Self { storage, limit: limit::OccupancyLimit::Limited(max_occupancy), bytes: 0 })
+    }
+
+    /// Attempts to reserve enough additional capacity in the backing storage so
+    /// that it can hold up to `target` elements without reallocating, reporting
+    /// allocation failure instead of aborting.
+    pub fn try_grow(&mut self, target: usize) -> Result<(), TryReserveError> {
+        let additional = target.saturating_sub(self.storage.len());
+        self.storage.try_reserve(additional)
+    }
+
+    /// Attempts to replace the occupancy limit of this [`VecCache<T>`] with
+    /// `new_limit`, growing the backing storage first if `new_limit` exceeds
+    /// current capacity.
+    ///
+    /// If the allocation needed to grow the backing storage fails, the limit
+    /// is left unchanged and the error is returned; otherwise, the previous
+    /// limit is returned, mirroring [`limit::OccupancyLimit::replace_limit`].
+    pub fn try_set_limit(&mut self, new_limit: usize) -> Result<Option<usize>, TryReserveError> {
+        self.try_grow(new_limit)?;
+        Ok(self.limit.replace_limit(new_limit))
     }
 
     /// Returns `true` if the cache is empty.
@@ -61,6 +101,16 @@ impl<T> VecCache<T> {
         self.storage.len()
     }
 
+    /// Returns the current running total of estimated memory footprint, in
+    /// bytes, of the elements held by this [`VecCache<T>`].
+    ///
+    /// This is always `0` unless `T` implements [`MemSize`] and the cache has
+    /// actually had values pushed through [`try_push_bytes`]/[`push_bytes`]/
+    /// [`push_evict_bytes`], which are the only methods that update it.
+    pub fn byte_size(&self) -> usize {
+        self.bytes
+    }
+
     /// Un-sets the maximum occupancy limit of this [`VecCache<T>`], returning the
     /// old limit.
     #[inline]
@@ -72,19 +122,173 @@ impl<T> VecCache<T> {
         self.limit.get()
     }
 
+    /// Attempts to append `value`, honoring a [`Limited`](limit::OccupancyLimit::Limited)
+    /// or [`Unlimited`](limit::OccupancyLimit::Unlimited) occupancy limit.
+    ///
+    /// A [`LimitedBytes`](limit::OccupancyLimit::LimitedBytes) cache (only
+    /// constructible via [`with_byte_limit`], which requires [`MemSize`]) is
+    /// not byte-budget-enforced through this method and admits `value`
+    /// unconditionally; use [`try_push_bytes`] instead to enforce the byte
+    /// budget.
     pub fn try_push(&mut self, value: T) -> Result<(), limit::OccupancyError> {
-        match self.limit.get() {
-            Some(0) => return Err(limit::OccupancyError::ZeroMaxOccupancy),
-            Some(lim) => {
+        match self.limit {
+            limit::OccupancyLimit::Unlimited | limit::OccupancyLimit::LimitedBytes(_) => (),
+            limit::OccupancyLimit::Limited(0) => return Err(limit::OccupancyError::ZeroMaxOccupancy),
+            limit::OccupancyLimit::Limited(lim) => {
                 if self.occupancy() + 1 > lim {
                     return Err(limit::OccupancyError::ReachedMaxOccupancy(lim))
                 }
             }
-            None => (),
         }
         self.storage.push_back(value);
         Ok(())
     }
+
+    /// Appends `value` to the cache, evicting the oldest element if doing so
+    /// would exceed a [`Limited`](limit::OccupancyLimit::Limited) occupancy limit.
+    ///
+    /// Unlike [`try_push`], this method is infallible: it never returns an error,
+    /// making good on the FIFO-drop behavior described at the type level. Under a
+    /// limit of `0`, `value` is dropped without being stored. See
+    /// [`try_push`] for how a [`LimitedBytes`](limit::OccupancyLimit::LimitedBytes)
+    /// cache is handled; use [`push_bytes`] to enforce the byte budget instead.
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        let _ = self.push_evict(value);
+    }
+
+    /// Appends `value` to the cache, returning the evicted element if occupancy
+    /// would otherwise exceed a [`Limited`](limit::OccupancyLimit::Limited) limit.
+    ///
+    /// Callers performing write-back caching can use the returned value to persist
+    /// state that is about to be dropped. Under a limit of `0`, `value` is
+    /// dropped without being stored and `None` is returned. See [`try_push`] for
+    /// how a [`LimitedBytes`](limit::OccupancyLimit::LimitedBytes) cache is handled;
+    /// use [`push_evict_bytes`] to enforce the byte budget instead.
+    pub fn push_evict(&mut self, value: T) -> Option<T> {
+        let evicted = match self.limit {
+            limit::OccupancyLimit::Unlimited | limit::OccupancyLimit::LimitedBytes(_) => None,
+            // A zero limit admits nothing; skip the push entirely rather than
+            // evicting (nothing to evict from an empty cache) and pushing anyway.
+            limit::OccupancyLimit::Limited(0) => return None,
+            limit::OccupancyLimit::Limited(lim) => {
+                if self.occupancy() + 1 > lim {
+                    self.storage.pop_front()
+                } else {
+                    None
+                }
+            }
+        };
+        self.storage.push_back(value);
+        evicted
+    }
+}
+
+/// Byte-budget-aware operations, requiring [`MemSize`] to estimate how much
+/// each value contributes to the running [`byte_size`](VecCache::byte_size)
+/// total enforced by a [`limit::OccupancyLimit::LimitedBytes`] budget.
+///
+/// These are distinct from [`try_push`]/[`push`]/[`push_evict`] (which remain
+/// usable by any `T`, even one with no [`MemSize`] impl) because a single
+/// method cannot be both generic over all `T` and conditionally call
+/// [`MemSize::mem_size`] only for the `T` that implement it. Mixing calls to
+/// the two families on the same [`LimitedBytes`](limit::OccupancyLimit::LimitedBytes)
+/// cache will desynchronize [`byte_size`](VecCache::byte_size) from the
+/// actual contents, since only this family updates it.
+impl<T: MemSize> VecCache<T> {
+    /// Creates a new [`VecCache<T>`] with a fixed upper bound on total estimated
+    /// memory footprint, in bytes, as reported by [`MemSize::mem_size`].
+    ///
+    /// This caps the cache by the variable size of its elements rather than by
+    /// their count, which suits caches of buffers, strings, or other
+    /// variably-sized values.
+    #[must_use]
+    #[inline]
+    pub fn with_byte_limit(max_bytes: usize) -> Self {
+        Self { storage: VecDeque::new(), limit: limit::OccupancyLimit::LimitedBytes(max_bytes), bytes: 0 }
+    }
+
+    /// Byte-budget-aware counterpart to [`try_push`](VecCache::try_push); see
+    /// the [impl block](VecCache#impl-VecCache<T>-1) documentation.
+    pub fn try_push_bytes(&mut self, value: T) -> Result<(), limit::OccupancyError> {
+        match self.limit {
+            limit::OccupancyLimit::Unlimited => (),
+            limit::OccupancyLimit::Limited(0) => return Err(limit::OccupancyError::ZeroMaxOccupancy),
+            limit::OccupancyLimit::Limited(lim) => {
+                if self.occupancy() + 1 > lim {
+                    return Err(limit::OccupancyError::ReachedMaxOccupancy(lim))
+                }
+            }
+            limit::OccupancyLimit::LimitedBytes(0) => return Err(limit::OccupancyError::ZeroMaxOccupancy),
+            limit::OccupancyLimit::LimitedBytes(budget) => {
+                if self.bytes + value.mem_size() > budget {
+                    return Err(limit::OccupancyError::ReachedMaxOccupancy(budget))
+                }
+            }
+        }
+        self.bytes += value.mem_size();
+        self.storage.push_back(value);
+        Ok(())
+    }
+
+    /// Byte-budget-aware counterpart to [`push`](VecCache::push); see
+    /// the [impl block](VecCache#impl-VecCache<T>-1) documentation.
+    #[inline]
+    pub fn push_bytes(&mut self, value: T) {
+        let _ = self.push_evict_bytes(value);
+    }
+
+    /// Byte-budget-aware counterpart to [`push_evict`](VecCache::push_evict);
+    /// see the [impl block](VecCache#impl-VecCache<T>-1) documentation.
+    ///
+    /// Under a [`limit::OccupancyLimit::LimitedBytes`] budget, more than one
+    /// oldest element may need to be evicted to make room for `value`; only
+    /// the most recently evicted one is returned.
+    pub fn push_evict_bytes(&mut self, value: T) -> Option<T> {
+        let mut evicted = None;
+        match self.limit {
+            limit::OccupancyLimit::Unlimited => (),
+            // A zero limit admits nothing; skip the push entirely rather than
+            // evicting (nothing to evict from an empty cache) and pushing anyway.
+            limit::OccupancyLimit::Limited(0) | limit::OccupancyLimit::LimitedBytes(0) => return None,
+            limit::OccupancyLimit::Limited(lim) => {
+                if self.occupancy() + 1 > lim {
+                    evicted = self.pop_front_tracked();
+                }
+            }
+            limit::OccupancyLimit::LimitedBytes(budget) => {
+                let incoming = value.mem_size();
+                while self.bytes + incoming > budget {
+                    match self.pop_front_tracked() {
+                        Some(old) => evicted = Some(old),
+                        None => break,
+                    }
+                }
+            }
+        }
+        self.bytes += value.mem_size();
+        self.storage.push_back(value);
+        evicted
+    }
+
+    /// Pops the front element, if any, and debits its estimated size from the
+    /// running [`byte_size`](VecCache::byte_size) total.
+    fn pop_front_tracked(&mut self) -> Option<T> {
+        let old = self.storage.pop_front()?;
+        self.bytes -= old.mem_size();
+        Some(old)
+    }
+}
+
+impl<T: Cacheable> VecCache<T> {
+    /// Returns an iterator over the entries currently holding unflushed
+    /// modifications, without removing them from the cache.
+    ///
+    /// A caller maintaining a backing store can use this to flush only
+    /// changed entries ahead of eviction, rather than the whole cache.
+    pub fn drain_dirty(&mut self) -> impl Iterator<Item = &T> {
+        self.storage.iter().filter(|v| v.dirty())
+    }
 }
 
 
@@ -99,7 +303,7 @@ impl<T> VecCache<T> {
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct LLCache<T> {
     storage: LinkedList<T>,
-    limit: Option<usize>,
+    limit: limit::OccupancyLimit,
 }
 
 impl<T> Default for LLCache<T> where LinkedList<T>: Default {
@@ -113,7 +317,14 @@ impl<T> LLCache<T> {
     #[inline]
     /// Creates an empty [LLCache<T>] with an unrestricted maximum occupancy
     pub fn new() -> Self {
-        Self { storage: LinkedList::new(), limit: None }
+        Self { storage: LinkedList::new(), limit: limit::OccupancyLimit::Unlimited }
+    }
+
+    /// Creates a new [`LLCache<T>`] with a fixed upper bound on maximum occupancy
+    #[must_use]
+    #[inline]
+    pub fn with_limit(max_occupancy: usize) -> Self {
+        Self { storage: LinkedList::new(), limit: limit::OccupancyLimit::Limited(max_occupancy) }
     }
 
     pub fn is_empty(&self) -> bool {
@@ -125,8 +336,174 @@ impl<T> LLCache<T> {
         self.storage.len()
     }
 
+    /// Un-sets the maximum occupancy limit of this [`LLCache<T>`], returning the
+    /// old limit.
+    #[inline]
+    pub fn unset_limit(&mut self) -> Option<usize> {
+        self.limit.unset_limit()
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.limit.get()
+    }
 
+    pub fn try_push(&mut self, value: T) -> Result<(), limit::OccupancyError> {
+        match self.limit.get() {
+            Some(0) => return Err(limit::OccupancyError::ZeroMaxOccupancy),
+            Some(lim) => {
+                if self.occupancy() + 1 > lim {
+                    return Err(limit::OccupancyError::ReachedMaxOccupancy(lim))
+                }
+            }
+            None => (),
+        }
+        self.storage.push_back(value);
+        Ok(())
+    }
+
+    /// Appends `value` to the cache, evicting the oldest element if doing so
+    /// would exceed the occupancy limit.
+    ///
+    /// Unlike [`try_push`], this method is infallible: it never returns an error,
+    /// making good on the FIFO-drop behavior described at the type level. Under a
+    /// limit of `0`, `value` is dropped without being stored.
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        let _ = self.push_evict(value);
+    }
 
+    /// Appends `value` to the cache, returning the evicted element if occupancy
+    /// would otherwise exceed the limit.
+    ///
+    /// Callers performing write-back caching can use the returned value to persist
+    /// state that is about to be dropped. Under a limit of `0`, `value` is
+    /// dropped without being stored and `None` is returned.
+    pub fn push_evict(&mut self, value: T) -> Option<T> {
+        // A zero limit admits nothing; skip the push entirely rather than
+        // evicting (nothing to evict from an empty cache) and pushing anyway.
+        if self.limit.is_zero() {
+            return None;
+        }
+        let evicted = match self.limit.get() {
+            Some(lim) if self.occupancy() + 1 > lim => self.storage.pop_front(),
+            _ => None,
+        };
+        self.storage.push_back(value);
+        evicted
+    }
+}
+
+impl<T: Cacheable> LLCache<T> {
+    /// Returns an iterator over the entries currently holding unflushed
+    /// modifications, without removing them from the cache.
+    ///
+    /// A caller maintaining a backing store can use this to flush only
+    /// changed entries ahead of eviction, rather than the whole cache.
+    pub fn drain_dirty(&mut self) -> impl Iterator<Item = &T> {
+        self.storage.iter().filter(|v| v.dirty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LLCache, VecCache};
+
+    #[test]
+    fn vec_cache_push_evict_drops_oldest_at_capacity() {
+        let mut cache: VecCache<u8> = VecCache::with_limit(2);
+        cache.push(1);
+        cache.push(2);
+        let evicted = cache.push_evict(3);
+        assert_eq!(evicted, Some(1));
+        assert_eq!(cache.occupancy(), 2);
+    }
+
+    #[test]
+    fn vec_cache_try_push_rejects_at_capacity() {
+        let mut cache: VecCache<u8> = VecCache::with_limit(1);
+        assert!(cache.try_push(1).is_ok());
+        assert!(cache.try_push(2).is_err());
+        assert_eq!(cache.occupancy(), 1);
+    }
+
+    #[test]
+    fn vec_cache_push_family_usable_without_mem_size() {
+        struct Opaque(u8);
+        let mut cache: VecCache<Opaque> = VecCache::with_limit(1);
+        assert!(cache.try_push(Opaque(1)).is_ok());
+        let evicted = cache.push_evict(Opaque(2));
+        assert_eq!(evicted.map(|o| o.0), Some(1));
+    }
+
+    #[test]
+    fn ll_cache_push_evict_drops_oldest_at_capacity() {
+        let mut cache: LLCache<u8> = LLCache::with_limit(2);
+        cache.push(1);
+        cache.push(2);
+        let evicted = cache.push_evict(3);
+        assert_eq!(evicted, Some(1));
+        assert_eq!(cache.occupancy(), 2);
+    }
+
+    #[test]
+    fn vec_cache_push_evict_bytes_evicts_to_stay_within_budget() {
+        let stack = std::mem::size_of::<String>();
+        let budget = stack + 16;
+        let mut cache: VecCache<String> = VecCache::with_byte_limit(budget);
+        cache.push_bytes(String::from("x"));
+        let evicted = cache.push_evict_bytes("y".repeat(16));
+        assert!(evicted.is_some());
+        assert_eq!(cache.byte_size(), budget);
+    }
+
+    #[test]
+    fn vec_cache_try_push_bytes_rejects_over_budget() {
+        let mut cache: VecCache<String> = VecCache::with_byte_limit(4);
+        assert!(cache.try_push_bytes("way too big".to_string()).is_err());
+        assert_eq!(cache.byte_size(), 0);
+    }
+
+    #[test]
+    fn vec_cache_drain_dirty_yields_only_unflushed_entries() {
+        use crate::cacheable::Dirty;
+        let mut cache: VecCache<Dirty<u8>> = VecCache::new();
+        cache.push(Dirty::clean(1));
+        cache.push(Dirty::new(2));
+        let dirty: Vec<&u8> = cache.drain_dirty().map(Dirty::get).collect();
+        assert_eq!(dirty, vec![&2]);
+    }
+
+    #[test]
+    fn vec_cache_push_evict_reports_evicted_dirty_state() {
+        use crate::cacheable::{Cacheable, Dirty};
+        let mut cache: VecCache<Dirty<u8>> = VecCache::with_limit(1);
+        cache.push(Dirty::new(1));
+        let evicted = cache.push_evict(Dirty::clean(2));
+        assert!(evicted.unwrap().dirty());
+    }
+
+    #[test]
+    fn vec_cache_try_with_limit_sets_limit_and_capacity() {
+        let cache: VecCache<u8> = VecCache::try_with_limit(4).unwrap();
+        assert_eq!(cache.limit(), Some(4));
+        assert!(cache.storage.capacity() >= 4);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn vec_cache_try_set_limit_returns_previous_limit() {
+        let mut cache: VecCache<u8> = VecCache::with_limit(2);
+        let previous = cache.try_set_limit(8).unwrap();
+        assert_eq!(previous, Some(2));
+        assert_eq!(cache.limit(), Some(8));
+    }
+
+    #[test]
+    fn vec_cache_try_grow_is_a_no_op_when_already_at_target() {
+        let mut cache: VecCache<u8> = VecCache::with_limit(4);
+        assert!(cache.try_grow(4).is_ok());
+        assert_eq!(cache.limit(), Some(4));
+    }
 }
 
 
@@ -7,12 +7,16 @@ pub enum OccupancyLimit {
     #[default]
     Unlimited,
     Limited(usize),
+    /// Caps occupancy by estimated total memory footprint (in bytes), as
+    /// reported by [`MemSize::mem_size`], rather than by element count.
+    LimitedBytes(usize),
 }
 
 impl PartialEq<usize> for OccupancyLimit {
     fn eq(&self, other: &usize) -> bool {
         match self {
             Self::Limited(lim) => lim.eq(other),
+            Self::LimitedBytes(lim) => lim.eq(other),
             Self::Unlimited => false,
         }
     }
@@ -24,6 +28,7 @@ impl PartialEq for OccupancyLimit {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Self::Limited(l0), Self::Limited(r0)) => l0 == r0,
+            (Self::LimitedBytes(l0), Self::LimitedBytes(r0)) => l0 == r0,
             (Self::Unlimited, Self::Unlimited) => true,
             _ => false,
         }
@@ -34,6 +39,7 @@ impl PartialOrd<usize> for OccupancyLimit {
     fn partial_cmp(&self, other: &usize) -> Option<std::cmp::Ordering> {
         match (self, other) {
             (Self::Limited(l0), r0) => Some(l0.cmp(r0)),
+            (Self::LimitedBytes(l0), r0) => Some(l0.cmp(r0)),
             (Self::Unlimited, _) => Some(std::cmp::Ordering::Greater)
         }
     }
@@ -41,12 +47,7 @@ impl PartialOrd<usize> for OccupancyLimit {
 
 impl PartialOrd for OccupancyLimit {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match (self, other) {
-            (Self::Limited(l0), Self::Limited(r0)) => Some(l0.cmp(r0)),
-            (Self::Unlimited, Self::Unlimited) => Some(std::cmp::Ordering::Equal),
-            (Self::Limited(_), Self::Unlimited) => Some(std::cmp::Ordering::Less),
-            (Self::Unlimited, Self::Limited(_)) => Some(std::cmp::Ordering::Greater),
-        }
+        Some(self.cmp(other))
     }
 }
 
@@ -54,9 +55,12 @@ impl Ord for OccupancyLimit {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         match (self, other) {
             (Self::Limited(l0), Self::Limited(r0)) => l0.cmp(r0),
+            (Self::LimitedBytes(l0), Self::LimitedBytes(r0)) => l0.cmp(r0),
             (Self::Unlimited, Self::Unlimited) => std::cmp::Ordering::Equal,
-            (Self::Limited(_), Self::Unlimited) => std::cmp::Ordering::Less,
-            (Self::Unlimited, Self::Limited(_)) => std::cmp::Ordering::Greater,
+            (Self::Unlimited, _) => std::cmp::Ordering::Greater,
+            (_, Self::Unlimited) => std::cmp::Ordering::Less,
+            (Self::Limited(_), Self::LimitedBytes(_)) => std::cmp::Ordering::Less,
+            (Self::LimitedBytes(_), Self::Limited(_)) => std::cmp::Ordering::Greater,
         }
     }
 }
@@ -68,7 +72,7 @@ impl OccupancyLimit {
     pub fn unset_limit(&mut self) -> Option<usize> {
         match self {
             Self::Unlimited => None,
-            &mut Self::Limited(val) => {
+            &mut Self::Limited(val) | &mut Self::LimitedBytes(val) => {
                 *self = Self::Unlimited;
                 Some(val)
             },
@@ -77,14 +81,14 @@ impl OccupancyLimit {
 
     /// Returns `true` if the [`OccupancyLimit`] is equal to `0`.
     pub const fn is_zero(&self) -> bool {
-        matches!(self, Self::Limited(0))
+        matches!(self, Self::Limited(0) | Self::LimitedBytes(0))
     }
 
     #[inline]
     pub const fn get(&self) -> Option<usize> {
         match self {
             Self::Unlimited => None,
-            &Self::Limited(val) => Some(val),
+            &Self::Limited(val) | &Self::LimitedBytes(val) => Some(val),
         }
     }
 
@@ -112,7 +116,7 @@ impl OccupancyLimit {
     pub fn get_mut(&mut self) -> Option<&mut usize> {
         match self {
             OccupancyLimit::Unlimited => None,
-            OccupancyLimit::Limited(ref mut value) => Some(value),
+            OccupancyLimit::Limited(ref mut value) | OccupancyLimit::LimitedBytes(ref mut value) => Some(value),
         }
     }
 
@@ -169,7 +173,7 @@ impl OccupancyLimit {
                 *self = Self::Limited(limit);
                 None
             },
-            &mut Self::Limited(old_value) => {
+            &mut Self::Limited(old_value) | &mut Self::LimitedBytes(old_value) => {
                 *self = Self::Limited(limit);
                 Some(old_value)
             }
@@ -177,6 +181,71 @@ impl OccupancyLimit {
     }
 }
 
+/// Trait for estimating the in-memory footprint of a value, in bytes.
+///
+/// Used by [`OccupancyLimit::LimitedBytes`] to cap cache occupancy by
+/// estimated memory footprint rather than by element count.
+///
+/// `mem_size` always reports the *total* footprint of `self` — its own
+/// stack-resident representation (`size_of::<Self>()`) plus whatever heap
+/// memory it owns — so that composite impls (e.g. [`MemSize for
+/// Vec<T>`](Vec)) can recover just the heap-owned portion of an element via
+/// `elem.mem_size() - size_of::<T>()` without under- or over-counting.
+pub trait MemSize {
+    /// Returns an estimate, in bytes, of how much memory `self` occupies.
+    fn mem_size(&self) -> usize;
+}
+
+macro_rules! impl_mem_size_for_sized {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl MemSize for $t {
+                #[inline]
+                fn mem_size(&self) -> usize {
+                    std::mem::size_of::<$t>()
+                }
+            }
+        )*
+    };
+}
+
+impl_mem_size_for_sized!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char);
+
+impl MemSize for String {
+    #[inline]
+    fn mem_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.capacity()
+    }
+}
+
+impl MemSize for &str {
+    /// Counts the bytes of the referenced string as well as the reference
+    /// itself, although a `&str` borrows those bytes rather than owning them.
+    #[inline]
+    fn mem_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.len()
+    }
+}
+
+impl<T: MemSize> MemSize for Vec<T> {
+    /// Reports its own stack footprint (`size_of::<Self>()`) plus the size of
+    /// the reserved allocation (`capacity() * size_of::<T>()`), matching
+    /// [`MemSize for String`](MemSize), plus whatever heap memory each live
+    /// element owns *beyond* its own stack footprint
+    /// (`element.mem_size() - size_of::<T>()`).
+    ///
+    /// Accounting for the backing array by capacity (rather than by summing
+    /// `T::mem_size()` over live elements) keeps a `VecCache<String>` and an
+    /// equivalent-memory `VecCache<Vec<u8>>` from drifting apart under the
+    /// same byte budget, while still crediting elements like `Vec<String>`
+    /// for the heap storage owned by each string.
+    fn mem_size(&self) -> usize {
+        let backing = std::mem::size_of::<Self>() + self.capacity() * std::mem::size_of::<T>();
+        let owned: usize = self.iter().map(|elem| elem.mem_size().saturating_sub(std::mem::size_of::<T>())).sum();
+        backing + owned
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OccupancyError {
     ZeroMaxOccupancy,
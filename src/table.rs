@@ -0,0 +1,147 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::multi::limit::OccupancyLimit;
+
+/// Keyed, N-way set-associative cache with a hard, fixed-capacity memory ceiling.
+///
+/// A [`CacheTable<K, V>`] is backed by a fixed number of "cache lines," each of
+/// which holds up to a small constant number of `(K, V)` entries (the line's
+/// "width"). Looking up a key hashes it to select a line and then linearly scans
+/// that line's entries, giving constant-time (with respect to total occupancy)
+/// approximate caching of expensive-computation results.
+///
+/// This complements the occupancy-bounded, positional [`VecCache`](crate::multi::VecCache):
+/// where `VecCache` retains the most recent values regardless of identity,
+/// `CacheTable` retains the most recent values *per key*, at the cost of being
+/// willing to evict a live entry whenever its line fills up, even if other lines
+/// are empty.
+#[derive(Clone, Debug)]
+pub struct CacheTable<K, V> {
+    lines: Vec<Vec<(K, V)>>,
+    line_width: usize,
+    limit: OccupancyLimit,
+}
+
+impl<K: Eq + Hash, V> CacheTable<K, V> {
+    /// Creates a new [`CacheTable<K, V>`] with `num_lines` cache lines, each
+    /// able to hold up to `line_width` entries.
+    ///
+    /// The total occupancy limit is the product of the two, and is reported by
+    /// [`limit`](CacheTable::limit).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_lines` or `line_width` is `0`, since a table with no
+    /// lines (or lines that can hold nothing) has no valid line to select or
+    /// insert into.
+    #[must_use]
+    pub fn new(num_lines: usize, line_width: usize) -> Self {
+        assert!(num_lines > 0, "CacheTable::new: num_lines must be greater than 0");
+        assert!(line_width > 0, "CacheTable::new: line_width must be greater than 0");
+        Self {
+            lines: (0..num_lines).map(|_| Vec::with_capacity(line_width)).collect(),
+            line_width,
+            limit: OccupancyLimit::Limited(num_lines * line_width),
+        }
+    }
+
+    /// Returns the number of cache lines in this [`CacheTable<K, V>`].
+    #[inline]
+    pub fn num_lines(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns the per-line width (maximum number of entries per cache line)
+    /// of this [`CacheTable<K, V>`].
+    #[inline]
+    pub fn line_width(&self) -> usize {
+        self.line_width
+    }
+
+    /// Returns the total occupancy limit (`num_lines * line_width`) of this
+    /// [`CacheTable<K, V>`].
+    #[inline]
+    pub fn limit(&self) -> Option<usize> {
+        self.limit.get()
+    }
+
+    /// Returns the current total occupancy (number of live entries) across all
+    /// cache lines.
+    pub fn occupancy(&self) -> usize {
+        self.lines.iter().map(Vec::len).sum()
+    }
+
+    /// Returns `true` if the table holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.lines.iter().all(Vec::is_empty)
+    }
+
+    /// Hashes `key` to the index of the cache line it is assigned to.
+    fn line_index(&self, key: &K) -> usize {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        (hasher.finish() as usize) % self.lines.len()
+    }
+
+    /// Returns a reference to the value associated with `key`, if it is
+    /// currently cached.
+    ///
+    /// This hashes `key` to its line and performs a linear scan of that line's
+    /// entries, so lookup cost is bounded by `line_width`, not total occupancy.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let idx = self.line_index(key);
+        self.lines[idx].iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Inserts `(key, value)` into the cache line selected by `key`, evicting
+    /// an existing entry from that line (in FIFO order) if it is already full.
+    ///
+    /// If `key` was already present in its line, its entry is replaced in
+    /// place and the previous `(key, value)` pair is returned. Otherwise, the
+    /// evicted entry (if any) is the oldest entry in the line, returned so that
+    /// a caller performing write-back can persist it before it is lost.
+    pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        let idx = self.line_index(&key);
+        let line = &mut self.lines[idx];
+        if let Some(pos) = line.iter().position(|(k, _)| k == &key) {
+            return Some(std::mem::replace(&mut line[pos], (key, value)));
+        }
+        let evicted = if line.len() >= self.line_width {
+            Some(line.remove(0))
+        } else {
+            None
+        };
+        line.push((key, value));
+        evicted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CacheTable;
+
+    #[test]
+    #[should_panic(expected = "num_lines must be greater than 0")]
+    fn zero_num_lines_panics() {
+        let _ = CacheTable::<u8, u8>::new(0, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "line_width must be greater than 0")]
+    fn zero_line_width_panics() {
+        let _ = CacheTable::<u8, u8>::new(4, 0);
+    }
+
+    #[test]
+    fn evicts_oldest_entry_in_line_when_full() {
+        let mut table: CacheTable<u8, u8> = CacheTable::new(1, 2);
+        assert_eq!(table.insert(1, 1), None);
+        assert_eq!(table.insert(2, 2), None);
+        let evicted = table.insert(3, 3);
+        assert_eq!(evicted, Some((1, 1)));
+        assert_eq!(table.get(&1), None);
+        assert_eq!(table.get(&2), Some(&2));
+        assert_eq!(table.get(&3), Some(&3));
+    }
+}